@@ -131,24 +131,60 @@ where
             .generate_proof(idx, &attrs_com)
             .expect("could not construct auth path");
 
-        // Construct the prover with all the relevant info, and prove
-        let prover: TreeMembershipProver<E::Fr, AC, ACG, H, HG> = TreeMembershipProver {
-            height: self.tree.height,
-            crh_param: self.tree.two_to_one_param.clone(),
-            attrs_com,
+        prove_membership_with_path(
+            rng,
+            pk,
+            self.tree.height,
+            self.tree.two_to_one_param.clone(),
+            auth_path,
             root,
-            auth_path: Some(auth_path),
-            _marker: PhantomData,
-        };
-
-        let proof = ark_groth16::create_random_proof(prover, &pk.pk, rng)?;
-        Ok(TreeProof {
-            proof,
-            _marker: PhantomData,
-        })
+            attrs_com,
+        )
     }
 }
 
+/// Proves that `attrs_com` is a member of the tree with the given `root`, given an auth path to
+/// it. This is the shared core of [`ComTree::prove_membership`], factored out so that other
+/// sources of auth paths — e.g. an incrementally-maintained
+/// [`Witness`](crate::incremental_tree::Witness) — can produce the same [`TreeProof`] without
+/// materializing a full [`ComTree`].
+pub(crate) fn prove_membership_with_path<R, E, ConstraintF, A, AC, ACG, H, HG>(
+    rng: &mut R,
+    pk: &TreeProvingKey<E, A, AC, ACG, H, HG>,
+    height: u32,
+    crh_param: H::Parameters,
+    auth_path: SparseMerkleTreePath<ComTreeConfig<H>>,
+    root: H::Output,
+    attrs_com: AC::Output,
+) -> Result<TreeProof<E, A, AC, ACG, H, HG>, SynthesisError>
+where
+    R: Rng,
+    E: PairingEngine<Fr = ConstraintF>,
+    ConstraintF: PrimeField,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme,
+    AC::Output: ToConstraintField<ConstraintF>,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF>,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+{
+    let prover: TreeMembershipProver<E::Fr, AC, ACG, H, HG> = TreeMembershipProver {
+        height,
+        crh_param,
+        attrs_com,
+        root,
+        auth_path: Some(auth_path),
+        _marker: PhantomData,
+    };
+
+    let proof = ark_groth16::create_random_proof(prover, &pk.pk, rng)?;
+    Ok(TreeProof {
+        proof,
+        _marker: PhantomData,
+    })
+}
+
 /// Generates the membership proving key for this tree
 pub fn gen_tree_memb_crs<R, E, A, AC, ACG, H, HG>(
     rng: &mut R,
@@ -0,0 +1,393 @@
+//! An append-only variant of [`ComTree`](crate::com_tree::ComTree) in the style of Zcash's
+//! `bridgetree`/`incrementalmerkletree`. An issuer who only ever appends commitments left-to-right
+//! can maintain a `Frontier` in O(log n) space and update it in O(log n) time per issuance, and a
+//! holder can keep their own [`Witness`] fresh the same way, without ever re-downloading the tree
+//! or re-fetching an auth path.
+
+use crate::{
+    attrs::Attrs,
+    com_tree::{prove_membership_with_path, ComTreeConfig},
+    identity_crh::IdentityCRH,
+    proof_data_structures::{TreeProof, TreeProvingKey},
+    sparse_merkle::SparseMerkleTreePath,
+};
+
+use core::marker::PhantomData;
+use std::collections::BTreeMap;
+
+use ark_crypto_primitives::{
+    commitment::{constraints::CommitmentGadget, CommitmentScheme},
+    crh::{constraints::TwoToOneCRHGadget, TwoToOneCRH},
+};
+use ark_ec::PairingEngine;
+use ark_ff::{to_bytes, ToConstraintField};
+use ark_relations::r1cs::SynthesisError;
+use ark_std::rand::Rng;
+
+/// The rightmost filled node at each level of an append-only tree, i.e., the minimal state needed
+/// to append a new leaf and recompute the root, without keeping the rest of the tree around.
+///
+/// This mirrors the "frontier" of Zcash's incremental Merkle tree: `ommers` holds one completed
+/// left node per set bit of `num_leaves`, ordered from the highest level down to the lowest, each
+/// waiting for the right sibling that a future append will fold into it.
+#[derive(Clone)]
+pub struct Frontier<H: TwoToOneCRH> {
+    /// The completed nodes still waiting for their right sibling, highest level first.
+    ommers: Vec<H::Output>,
+    /// The number of leaves appended so far.
+    num_leaves: u64,
+}
+
+impl<H: TwoToOneCRH> Frontier<H>
+where
+    H::Output: Clone + Default,
+{
+    /// Creates an empty frontier, i.e., one with no leaves appended.
+    pub fn empty() -> Self {
+        Frontier {
+            ommers: Vec::new(),
+            num_leaves: 0,
+        }
+    }
+
+    /// The number of leaves appended to this frontier so far.
+    pub fn num_leaves(&self) -> u64 {
+        self.num_leaves
+    }
+
+    /// Appends a leaf to the frontier, folding it up through any subtrees it completes. Returns
+    /// every newly completed node that is a *right* child, i.e. each node that gets folded into a
+    /// saved ommer, paired with the level of the *node itself* (not the level it folds into), in
+    /// bottom-up order. The topmost node produced by the fold is saved as an ommer, not returned.
+    ///
+    /// A holder maintaining a [`Witness`] should feed each `(level, root)` pair to
+    /// [`Witness::update`] in order.
+    pub fn append(&mut self, crh_param: &H::Parameters, leaf: H::Output) -> Vec<(u32, H::Output)> {
+        let mut completed = Vec::new();
+        let mut node = leaf;
+        let mut idx = self.num_leaves;
+        let mut level = 0u32;
+
+        // Walk up from the leaf, combining with the saved ommer at each level that we complete.
+        // We complete level `level` exactly when the leaf index is odd at that level, i.e., we're
+        // the right child of a subtree whose left child was saved as an ommer on a prior append.
+        // `node` is recorded as the completed sibling at `level` *before* it gets hashed with its
+        // own left ommer, since it is the level-`level` node that a same-level witness needs, not
+        // the level-`(level + 1)` node it folds into.
+        while idx & 1 == 1 {
+            let left = self
+                .ommers
+                .pop()
+                .expect("frontier is missing an ommer for a level it claims to have completed");
+            completed.push((level, node.clone()));
+            let left_bytes = to_bytes!(left).unwrap();
+            let node_bytes = to_bytes!(node).unwrap();
+            node = H::evaluate(crh_param, &left_bytes, &node_bytes).expect("failed to hash frontier node");
+            idx >>= 1;
+            level += 1;
+        }
+        self.ommers.push(node);
+        self.num_leaves += 1;
+
+        completed
+    }
+}
+
+/// A holder's membership witness for a single leaf in an [`Frontier`]-backed tree. Unlike a
+/// [`SparseMerkleTreePath`], which must be fetched fresh from the whole tree after every
+/// issuance, a `Witness` absorbs each newly appended leaf in O(1) amortized work via
+/// [`Witness::update`].
+#[derive(Clone)]
+pub struct Witness<H: TwoToOneCRH> {
+    /// This leaf's index in the tree.
+    position: u64,
+    /// The sibling known at each level, keyed by level. Levels at which this leaf's position is a
+    /// *right* child are known as soon as the witness is created, by reading the frontier's
+    /// current ommers; levels at which it's a *left* child are only known once a later append
+    /// completes that subtree, and arrive out of order relative to the right-child levels. A
+    /// `BTreeMap` (rather than a dense `Vec`) is what lets the two be merged regardless of arrival
+    /// order.
+    siblings: BTreeMap<u32, H::Output>,
+}
+
+impl<H: TwoToOneCRH> Witness<H>
+where
+    H::Output: Clone + Default,
+{
+    /// Starts a witness for the leaf about to be appended at `position`, seeding it with the
+    /// siblings already fixed by `frontier`'s ommers. `position` must equal
+    /// `frontier.num_leaves()`: a witness can only be created for the very next leaf, since that's
+    /// the only position whose right-child siblings are exactly `frontier`'s current ommers.
+    pub fn new(position: u64, frontier: &Frontier<H>) -> Self {
+        assert_eq!(
+            frontier.num_leaves, position,
+            "a witness can only be created for the leaf about to be appended to the frontier"
+        );
+
+        // `frontier.ommers` holds one node per set bit of `num_leaves`, ordered from the highest
+        // set level down to the lowest. Those are exactly the levels at which `position` is a
+        // right child (a right child's sibling is already complete), so zip the two in the same
+        // high-to-low order to pair each ommer with its level.
+        let mut siblings = BTreeMap::new();
+        let right_child_levels = (0..64u32).rev().filter(|level| (position >> level) & 1 == 1);
+        for (level, ommer) in right_child_levels.zip(frontier.ommers.iter()) {
+            siblings.insert(level, ommer.clone());
+        }
+
+        Witness { position, siblings }
+    }
+
+    /// This witness's leaf index.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Absorbs a subtree root that was newly completed at `level` (as emitted by
+    /// [`Frontier::append`]). If this witness's leaf is the left child of the completed subtree,
+    /// the completed root becomes this witness's sibling at that level; otherwise the update is a
+    /// no-op, since the completed subtree is not adjacent to this leaf.
+    pub fn update(&mut self, level: u32, completed_root: &H::Output) {
+        let is_left_child_at_level = (self.position >> level) & 1 == 0;
+
+        // Only the first node completed at a left-child level after this leaf was issued is its
+        // sibling; any later one at the same level lies further to the right.
+        if is_left_child_at_level {
+            self.siblings
+                .entry(level)
+                .or_insert_with(|| completed_root.clone());
+        }
+    }
+
+    /// Whether this witness has a sibling recorded for every level up to `height`, i.e., whether
+    /// it can currently produce a membership proof.
+    pub fn is_complete(&self, height: u32) -> bool {
+        (0..height).all(|level| self.siblings.contains_key(&level))
+    }
+
+    /// Converts this witness into the [`SparseMerkleTreePath`] shape used by `check_membership`,
+    /// so it can be fed into the same circuit that verifies a path fetched from a full tree.
+    /// `height` must be the same tree height the witness is being proven against. Each inner level
+    /// is a `(left, right)` pair holding this leaf's ancestor and its sibling, with the ancestor
+    /// recomputed from the leaf upward using `crh_param`.
+    pub(crate) fn to_auth_path<AC>(
+        &self,
+        crh_param: &H::Parameters,
+        height: u32,
+        leaf: &AC::Output,
+    ) -> SparseMerkleTreePath<ComTreeConfig<H>>
+    where
+        AC: CommitmentScheme,
+    {
+        let leaf_sibling = self.siblings.get(&0).cloned().unwrap_or_default();
+        let leaf_bytes = to_bytes!(leaf).unwrap();
+        let sibling_bytes = to_bytes!(leaf_sibling).unwrap();
+        let leaf_hashes = if self.position & 1 == 0 {
+            (leaf_bytes, sibling_bytes)
+        } else {
+            (sibling_bytes, leaf_bytes)
+        };
+
+        let mut node = H::evaluate(crh_param, &leaf_hashes.0, &leaf_hashes.1)
+            .expect("failed to hash auth path node");
+        let num_inner = height.checked_sub(2).expect("tree height cannot be < 2");
+        let inner_hashes = (1..=num_inner)
+            .map(|level| {
+                let sibling = self.siblings.get(&level).cloned().unwrap_or_default();
+                let bit = (self.position >> level) & 1;
+                let pair = if bit == 0 {
+                    (node.clone(), sibling)
+                } else {
+                    (sibling, node.clone())
+                };
+                let left_bytes = to_bytes!(pair.0).unwrap();
+                let right_bytes = to_bytes!(pair.1).unwrap();
+                node = H::evaluate(crh_param, &left_bytes, &right_bytes)
+                    .expect("failed to hash auth path node");
+                pair
+            })
+            .collect();
+
+        SparseMerkleTreePath {
+            leaf_hashes,
+            inner_hashes,
+        }
+    }
+}
+
+/// A checkpoint of an issuer's [`Frontier`] (or a holder's [`Witness`]) that can be restored with
+/// [`rewind`], to tolerate reorgs of the issuance log that appends were derived from.
+#[derive(Clone)]
+pub struct Checkpoint<T: Clone> {
+    state: T,
+}
+
+/// Snapshots `state` so it can later be restored with [`rewind`].
+pub fn checkpoint<T: Clone>(state: &T) -> Checkpoint<T> {
+    Checkpoint {
+        state: state.clone(),
+    }
+}
+
+/// Restores `state` to what it was when `checkpoint` was taken.
+pub fn rewind<T: Clone>(state: &mut T, checkpoint: &Checkpoint<T>) {
+    *state = checkpoint.state.clone();
+}
+
+/// Proves that the given attribute commitment, together with its incrementally-maintained
+/// `witness`, is a member of the tree with the given `root`. This is the incremental-tree
+/// counterpart to [`ComTree::prove_membership`](crate::com_tree::ComTree::prove_membership): it
+/// takes a [`Witness`] instead of an index into a materialized tree, but produces the exact same
+/// [`TreeProof`].
+pub fn prove_membership<R, E, A, AC, ACG, H, HG>(
+    rng: &mut R,
+    pk: &TreeProvingKey<E, A, AC, ACG, H, HG>,
+    height: u32,
+    crh_param: H::Parameters,
+    witness: &Witness<H>,
+    root: H::Output,
+    attrs_com: AC::Output,
+) -> Result<TreeProof<E, A, AC, ACG, H, HG>, SynthesisError>
+where
+    R: Rng,
+    E: PairingEngine,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme,
+    AC::Output: ToConstraintField<E::Fr>,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<E::Fr> + Clone + Default,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+{
+    assert!(
+        witness.is_complete(height - 1),
+        "witness is missing siblings; has it absorbed every append since its leaf was issued?"
+    );
+
+    let auth_path = witness.to_auth_path::<AC>(&crh_param, height, &attrs_com);
+    prove_membership_with_path(rng, pk, height, crh_param, auth_path, root, attrs_com)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::com_tree::{gen_tree_memb_crs, verify_tree_memb, ComTree};
+    use crate::test_util::{
+        NameAndBirthYear, TestComScheme, TestComSchemeG, TestTreeH, TestTreeHG, MERKLE_CRH_PARAM,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;
+
+    /// After a frontier appends `n` leaves, every leaf's witness, updated with each append's
+    /// completed nodes, should hold a sibling at every level of the tree.
+    #[test]
+    fn test_frontier_matches_witnesses() {
+        let crh_param = MERKLE_CRH_PARAM.clone();
+        let height = 4u32;
+        let num_leaves = 1u64 << (height - 1);
+
+        let mut frontier = Frontier::<TestTreeH>::empty();
+        let mut witnesses: Vec<Witness<TestTreeH>> = Vec::new();
+
+        for i in 0..num_leaves {
+            // Each witness must be created exactly when the tree reaches its own position, since
+            // that's the only point at which `frontier`'s ommers are the witness's right-child
+            // siblings.
+            witnesses.push(Witness::new(i, &frontier));
+
+            let leaf = <TestTreeH as TwoToOneCRH>::Output::default();
+            let completed = frontier.append(&crh_param, leaf);
+            for w in witnesses.iter_mut() {
+                for (level, root) in &completed {
+                    w.update(*level, root);
+                }
+            }
+            assert_eq!(frontier.num_leaves(), i + 1);
+        }
+
+        for w in &witnesses {
+            assert!(w.is_complete(height - 1));
+        }
+    }
+
+    /// Checkpointing a witness and then rewinding after further updates should restore it to its
+    /// earlier, less-complete state.
+    #[test]
+    fn test_checkpoint_rewind() {
+        let frontier = Frontier::<TestTreeH>::empty();
+        let mut witness = Witness::<TestTreeH>::new(0, &frontier);
+        let cp = checkpoint(&witness);
+
+        witness.update(0, &<TestTreeH as TwoToOneCRH>::Output::default());
+        assert_eq!(witness.siblings.len(), 1);
+
+        rewind(&mut witness, &cp);
+        assert_eq!(witness.siblings.len(), 0);
+    }
+
+    /// A holder who only ever absorbs `Frontier::append` updates into their `Witness` (never
+    /// fetching a full auth path) should still be able to produce a membership proof that verifies
+    /// against the root of a `ComTree` built from the same commitments.
+    #[test]
+    fn test_incremental_prove_membership() {
+        let mut rng = ark_std::test_rng();
+        let tree_height = 4u32;
+        let num_leaves = 1u64 << (tree_height - 1);
+        let crh_param = MERKLE_CRH_PARAM.clone();
+
+        let pk = gen_tree_memb_crs::<
+            _,
+            E,
+            NameAndBirthYear,
+            TestComScheme,
+            TestComSchemeG,
+            TestTreeH,
+            TestTreeHG,
+        >(&mut rng, crh_param.clone(), tree_height)
+        .unwrap();
+        let vk = pk.prepare_verifying_key();
+
+        let witness_idx = 3u64;
+        let mut tree = ComTree::<_, TestTreeH, TestComScheme>::empty(crh_param.clone(), tree_height);
+        let mut frontier = Frontier::<TestTreeH>::empty();
+        let mut witness = None;
+        let mut witness_com = None;
+
+        for i in 0..num_leaves {
+            // The witness can only be seeded from the frontier right as the tree reaches its own
+            // position, so create it just before appending that leaf.
+            if i == witness_idx {
+                witness = Some(Witness::<TestTreeH>::new(i, &frontier));
+            }
+
+            let person = NameAndBirthYear::new(&mut rng, b"Andrew", 1990 + i as u16);
+            let com = person.commit();
+            tree.insert(i, &com);
+            if i == witness_idx {
+                witness_com = Some(com.clone());
+            }
+            for (level, root) in frontier.append(&crh_param, com) {
+                if let Some(w) = witness.as_mut() {
+                    w.update(level, &root);
+                }
+            }
+        }
+
+        let witness = witness.unwrap();
+
+        assert!(witness.is_complete(tree_height - 1));
+        let witness_com = witness_com.unwrap();
+
+        let proof = prove_membership(
+            &mut rng,
+            &pk,
+            tree_height,
+            crh_param,
+            &witness,
+            tree.root(),
+            witness_com.clone(),
+        )
+        .unwrap();
+
+        assert!(verify_tree_memb(&vk, &proof, &witness_com, &tree.root()).unwrap());
+    }
+}
@@ -0,0 +1,445 @@
+//! A k-ary generalization of [`ComTree`](crate::com_tree::ComTree). A height-`h` binary tree
+//! needs `h - 1` two-to-one hashes on the path to the root; grouping every `log2(ARITY)` binary
+//! levels into one k-ary level cuts the path length by that factor, following the `insert`-gadget
+//! approach used by the storage-proofs PoR circuit: each level witnesses `ARITY - 1` siblings plus `log2(ARITY)` index
+//! bits, and the circuit reconstructs the ordered `ARITY`-element child row by conditionally
+//! inserting the current node at the witnessed position before hashing.
+//!
+//! `ARITY` must be a power of two (4 or 8 in practice). Rather than introduce a new arity-k hash
+//! family, a k-ary node is folded down to one digest with `ARITY - 1` calls to the existing binary
+//! [`TwoToOneCRH`], so going from height-32 binary to height-16 quaternary or height-11 octary
+//! needs no new CRS generation beyond the usual per-circuit setup. A path of `height` levels
+//! therefore costs `height * (ARITY - 1)` two-to-one hash gadgets, so the in-circuit hash count is
+//! not reduced: a height-16 quaternary path lays down 48, more than a height-32 binary path.
+//!
+//! This tree requires the attribute commitment scheme's output to coincide with the two-to-one
+//! hash's output (`AC::Output = H::Output`, and likewise for their gadgets), so that leaves and
+//! inner nodes share one digest type throughout — unlike [`ComTree`], which uses `IdentityCRH` to
+//! let leaves be arbitrary commitment outputs. This is a deliberate simplification: the binary
+//! tree's identity-leaf-hash trick doesn't carry over cleanly to a variable-arity fold, and the
+//! instantiations this tree is meant for (Poseidon commitments over the same field as the Poseidon
+//! two-to-one hash) already satisfy it.
+
+use crate::{
+    attrs::Attrs,
+    proof_data_structures::{TreeProof, TreeProvingKey},
+};
+
+use core::marker::PhantomData;
+use std::collections::BTreeMap;
+
+use ark_crypto_primitives::{
+    commitment::{constraints::CommitmentGadget, CommitmentScheme},
+    crh::{constraints::TwoToOneCRHGadget, TwoToOneCRH},
+};
+use ark_ec::PairingEngine;
+use ark_ff::{to_bytes, PrimeField, ToConstraintField};
+use ark_r1cs_std::{
+    alloc::AllocVar, bits::ToBytesGadget, boolean::Boolean, eq::EqGadget, select::CondSelectGadget,
+};
+use ark_relations::{
+    ns,
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError},
+};
+use ark_std::rand::Rng;
+
+/// The witness data for one level of a k-ary auth path: the `ARITY - 1` siblings of the node
+/// being proven, in left-to-right order as if that node were removed from the row, plus the index
+/// (`0..ARITY`) it occupies among them.
+#[derive(Clone)]
+pub struct KarySiblings<H: TwoToOneCRH> {
+    pub siblings: Vec<H::Output>,
+    pub index: usize,
+}
+
+/// An auth path through a k-ary tree of arity `ARITY`: one [`KarySiblings`] per level, from the
+/// leaf's row up to (but not including) the root.
+#[derive(Clone)]
+pub struct KaryMerkleTreePath<H: TwoToOneCRH, const ARITY: usize> {
+    pub levels: Vec<KarySiblings<H>>,
+}
+
+impl<H: TwoToOneCRH, const ARITY: usize> KaryMerkleTreePath<H, ARITY>
+where
+    H::Output: Default,
+{
+    /// A path of all-default siblings, for use when generating the membership-proving CRS, where
+    /// no real path is available — only its shape.
+    fn default_with_height(height: u32) -> Self {
+        KaryMerkleTreePath {
+            levels: vec![
+                KarySiblings {
+                    siblings: vec![H::Output::default(); ARITY - 1],
+                    index: 0,
+                };
+                height as usize
+            ],
+        }
+    }
+}
+
+/// Folds an ordered row of `ARITY` children down to a single node with `ARITY - 1` binary
+/// two-to-one hashes, left to right: `H(...H(H(c_0, c_1), c_2)..., c_{ARITY-1})`.
+fn fold_children<H: TwoToOneCRH>(crh_param: &H::Parameters, children: &[H::Output]) -> H::Output {
+    let mut acc = children[0].clone();
+    for child in &children[1..] {
+        let acc_bytes = to_bytes!(acc).unwrap();
+        let child_bytes = to_bytes!(child).unwrap();
+        acc = H::evaluate(crh_param, &acc_bytes, &child_bytes).expect("failed to hash k-ary node");
+    }
+    acc
+}
+
+/// A k-ary Merkle tree of attribute commitments, indexed in base `ARITY`.
+pub struct KaryComTree<ConstraintF, H, AC, const ARITY: usize>
+where
+    ConstraintF: PrimeField,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF>,
+    AC: CommitmentScheme<Output = H::Output>,
+{
+    height: u32,
+    crh_param: H::Parameters,
+    /// Every node, keyed by `(level, index within level)`, with level 0 being the leaves. Absent
+    /// entries are treated as `H::Output::default()`.
+    nodes: BTreeMap<(u32, u64), H::Output>,
+    _marker: PhantomData<(ConstraintF, AC)>,
+}
+
+impl<ConstraintF, H, AC, const ARITY: usize> KaryComTree<ConstraintF, H, AC, ARITY>
+where
+    ConstraintF: PrimeField,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF> + Clone + Default,
+    AC: CommitmentScheme<Output = H::Output>,
+{
+    /// Makes an empty tree with capacity `ARITY^tree_height`. `ARITY` must be a power of two and
+    /// `tree_height` must be at least 1.
+    pub fn empty(crh_param: H::Parameters, tree_height: u32) -> Self {
+        assert!(ARITY.is_power_of_two(), "ARITY must be a power of two");
+        assert!(tree_height >= 1, "tree height must be at least 1");
+        KaryComTree {
+            height: tree_height,
+            crh_param,
+            nodes: BTreeMap::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn child_row(&self, level: u32, group_start: u64) -> Vec<H::Output> {
+        (0..ARITY as u64)
+            .map(|offset| {
+                self.nodes
+                    .get(&(level, group_start + offset))
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Inserts a commitment at index `idx`, recomputing every ancestor on the path to the root.
+    pub fn insert(&mut self, idx: u64, com: &AC::Output) {
+        self.nodes.insert((0, idx), com.clone());
+
+        let mut cur_idx = idx;
+        for level in 0..self.height {
+            let parent_idx = cur_idx / ARITY as u64;
+            let group_start = parent_idx * ARITY as u64;
+            let row = self.child_row(level, group_start);
+            let parent = fold_children::<H>(&self.crh_param, &row);
+            self.nodes.insert((level + 1, parent_idx), parent);
+            cur_idx = parent_idx;
+        }
+    }
+
+    /// Returns this tree's root.
+    pub fn root(&self) -> H::Output {
+        self.nodes
+            .get(&(self.height, 0))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Builds the auth path for the leaf at `idx`.
+    pub fn generate_proof(&self, idx: u64) -> KaryMerkleTreePath<H, ARITY> {
+        let mut levels = Vec::with_capacity(self.height as usize);
+        let mut cur_idx = idx;
+        for level in 0..self.height {
+            let group_start = (cur_idx / ARITY as u64) * ARITY as u64;
+            let index = (cur_idx % ARITY as u64) as usize;
+            let mut row = self.child_row(level, group_start);
+            row.remove(index);
+            levels.push(KarySiblings {
+                siblings: row,
+                index,
+            });
+            cur_idx /= ARITY as u64;
+        }
+        KaryMerkleTreePath { levels }
+    }
+
+    /// Proves that the given attribute commitment is at the specified tree index.
+    pub fn prove_membership<R, E, A, ACG, HG>(
+        &self,
+        rng: &mut R,
+        pk: &TreeProvingKey<E, A, AC, ACG, H, HG>,
+        idx: u64,
+        attrs_com: AC::Output,
+    ) -> Result<TreeProof<E, A, AC, ACG, H, HG>, SynthesisError>
+    where
+        R: Rng,
+        E: PairingEngine<Fr = ConstraintF>,
+        A: Attrs<E::Fr, AC>,
+        ACG: CommitmentGadget<AC, E::Fr>,
+        HG: TwoToOneCRHGadget<H, E::Fr>,
+        HG::OutputVar: ToBytesGadget<E::Fr>,
+    {
+        let root = self.root();
+        let auth_path = self.generate_proof(idx);
+
+        let prover: KaryTreeMembershipProver<E::Fr, AC, ACG, H, HG, ARITY> =
+            KaryTreeMembershipProver {
+                height: self.height,
+                crh_param: self.crh_param.clone(),
+                attrs_com,
+                root,
+                auth_path: Some(auth_path),
+                _marker: PhantomData,
+            };
+
+        let proof = ark_groth16::create_random_proof(prover, &pk.pk, rng)?;
+        Ok(TreeProof {
+            proof,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Generates the membership proving key for a k-ary tree of the given height.
+pub fn gen_kary_tree_memb_crs<R, E, A, AC, ACG, H, HG, const ARITY: usize>(
+    rng: &mut R,
+    crh_param: H::Parameters,
+    height: u32,
+) -> Result<TreeProvingKey<E, A, AC, ACG, H, HG>, SynthesisError>
+where
+    R: Rng,
+    E: PairingEngine,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme<Output = H::Output>,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<E::Fr> + Default,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+    HG::OutputVar: ToBytesGadget<E::Fr>,
+{
+    let prover: KaryTreeMembershipProver<E::Fr, AC, ACG, H, HG, ARITY> =
+        KaryTreeMembershipProver {
+            height,
+            crh_param,
+            attrs_com: Default::default(),
+            root: Default::default(),
+            auth_path: None,
+            _marker: PhantomData,
+        };
+    let pk = ark_groth16::generate_random_parameters(prover, rng)?;
+    Ok(TreeProvingKey {
+        pk,
+        _marker: PhantomData,
+    })
+}
+
+/// A circuit that proves that a commitment to `attrs` appears in the k-ary tree of height
+/// `height` and arity `ARITY` defined by root hash `root`. Verification is enforced exactly as in
+/// the binary `TreeMembershipProver` (see [`crate::com_tree`]): recompute the root from the leaf
+/// and compare against the public `root_var` input. The only difference is that each level folds
+/// `ARITY` children instead of 2.
+struct KaryTreeMembershipProver<ConstraintF, AC, ACG, H, HG, const ARITY: usize>
+where
+    ConstraintF: PrimeField,
+    AC: CommitmentScheme<Output = H::Output>,
+    ACG: CommitmentGadget<AC, ConstraintF>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF>,
+    HG: TwoToOneCRHGadget<H, ConstraintF>,
+{
+    height: u32,
+    crh_param: H::Parameters,
+
+    attrs_com: AC::Output,
+    root: H::Output,
+    auth_path: Option<KaryMerkleTreePath<H, ARITY>>,
+
+    _marker: PhantomData<(ConstraintF, AC, ACG, HG)>,
+}
+
+impl<ConstraintF, AC, ACG, H, HG, const ARITY: usize> ConstraintSynthesizer<ConstraintF>
+    for KaryTreeMembershipProver<ConstraintF, AC, ACG, H, HG, ARITY>
+where
+    ConstraintF: PrimeField,
+    AC: CommitmentScheme<Output = H::Output>,
+    ACG: CommitmentGadget<AC, ConstraintF>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF> + Default,
+    HG: TwoToOneCRHGadget<H, ConstraintF>,
+    HG::OutputVar: ToBytesGadget<ConstraintF>,
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<ConstraintF>,
+    ) -> Result<(), SynthesisError> {
+        // Witness the public variables, as in every zeronym membership proof: the commitment to
+        // the attributes and the merkle root.
+        let attrs_com_var =
+            ACG::OutputVar::new_input(ns!(cs, "attrs com var"), || Ok(self.attrs_com))?;
+        let root_var = HG::OutputVar::new_input(ns!(cs, "root var"), || Ok(self.root))?;
+        let crh_param_var =
+            HG::ParametersVar::new_constant(ns!(cs, "two_to_one param"), &self.crh_param)?;
+
+        // If there is no auth path (CRS generation), make one of the right shape.
+        let auth_path = self
+            .auth_path
+            .unwrap_or_else(|| KaryMerkleTreePath::default_with_height(self.height));
+        let index_bits = ARITY.trailing_zeros() as usize;
+
+        let mut cur = attrs_com_var;
+        for level in auth_path.levels {
+            let index_var =
+                allocate_index_bits(ns!(cs, "kary index bits").cs(), level.index, index_bits)?;
+            let sibling_vars = level
+                .siblings
+                .iter()
+                .map(|s| HG::OutputVar::new_witness(ns!(cs, "kary sibling"), || Ok(s.clone())))
+                .collect::<Result<Vec<_>, _>>()?;
+            let row = conditionally_insert(&sibling_vars, &index_var, &cur)?;
+            cur = fold_children_gadget::<ConstraintF, H, HG>(&crh_param_var, &row)?;
+        }
+
+        cur.enforce_equal(&root_var)
+    }
+}
+
+/// Allocates the `num_bits` little-endian bits of `index` as witnesses.
+fn allocate_index_bits<ConstraintF: PrimeField>(
+    cs: ConstraintSystemRef<ConstraintF>,
+    index: usize,
+    num_bits: usize,
+) -> Result<Vec<Boolean<ConstraintF>>, SynthesisError> {
+    (0..num_bits)
+        .map(|i| Boolean::new_witness(cs.clone(), || Ok((index >> i) & 1 == 1)))
+        .collect()
+}
+
+/// Reconstructs the ordered `ARITY`-element child row from `ARITY - 1` siblings and `value`, by
+/// conditionally inserting `value` at the position selected by `index` — a cascade of
+/// `conditionally_select`s over the sibling slots, as in the storage-proofs PoR `insert` gadget.
+///
+/// `siblings` holds the row with `value` removed, i.e. `siblings[slot]` for `slot < index` and
+/// `siblings[slot - 1]` for `slot > index` (the slot at `index` itself is `value`). `index_bits`
+/// is the in-circuit witness of `index`; both the value slot and the sibling each other slot takes
+/// are selected from it in-circuit, so the circuit's shape does not depend on `index`.
+fn conditionally_insert<ConstraintF, V>(
+    siblings: &[V],
+    index_bits: &[Boolean<ConstraintF>],
+    value: &V,
+) -> Result<Vec<V>, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    V: Clone + CondSelectGadget<ConstraintF>,
+{
+    let arity = siblings.len() + 1;
+    let mut row = Vec::with_capacity(arity);
+    // Whether `value` sits at a slot strictly before the current one
+    let mut value_before = Boolean::FALSE;
+    for slot in 0..arity {
+        let is_value_slot = index_equals(index_bits, slot)?;
+        let sibling = if slot == 0 {
+            siblings[0].clone()
+        } else if slot == arity - 1 {
+            siblings[slot - 1].clone()
+        } else {
+            V::conditionally_select(&value_before, &siblings[slot - 1], &siblings[slot])?
+        };
+        row.push(V::conditionally_select(&is_value_slot, value, &sibling)?);
+        value_before = value_before.or(&is_value_slot)?;
+    }
+    Ok(row)
+}
+
+/// Whether the bits in `index_bits` (little-endian) equal the constant `slot`.
+fn index_equals<ConstraintF: PrimeField>(
+    index_bits: &[Boolean<ConstraintF>],
+    slot: usize,
+) -> Result<Boolean<ConstraintF>, SynthesisError> {
+    let mut acc = Boolean::TRUE;
+    for (i, bit) in index_bits.iter().enumerate() {
+        let want = (slot >> i) & 1 == 1;
+        let matches = if want { bit.clone() } else { bit.not() };
+        acc = acc.and(&matches)?;
+    }
+    Ok(acc)
+}
+
+/// Folds a row of `HG::OutputVar`s down to one node with `row.len() - 1` binary two-to-one hash
+/// gadget calls, mirroring [`fold_children`] in-circuit.
+fn fold_children_gadget<ConstraintF, H, HG>(
+    crh_param_var: &HG::ParametersVar,
+    row: &[HG::OutputVar],
+) -> Result<HG::OutputVar, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    H: TwoToOneCRH,
+    HG: TwoToOneCRHGadget<H, ConstraintF>,
+    HG::OutputVar: ToBytesGadget<ConstraintF>,
+{
+    let mut acc = row[0].clone();
+    for node in &row[1..] {
+        let acc_bytes = acc.to_bytes()?;
+        let node_bytes = node.to_bytes()?;
+        acc = HG::evaluate(crh_param_var, &acc_bytes, &node_bytes)?;
+    }
+    Ok(acc)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::com_tree::verify_tree_memb;
+    use crate::proof_data_structures::TreeVerifyingKey;
+    use crate::test_util::{PoseidonComScheme, PoseidonComSchemeG, PoseidonTreeH, PoseidonTreeHG};
+
+    use ark_bls12_381::{Bls12_381 as E, Fr};
+
+    /// A quaternary (arity-4) tree of height 4 holds as many leaves as a binary tree of height 8,
+    /// but its auth path is half as long.
+    #[test]
+    fn test_kary_tree_proof() {
+        let mut rng = ark_std::test_rng();
+        let tree_height = 4;
+        let crh_param = PoseidonTreeH::setup(&mut rng).unwrap();
+        let attrs_com = Fr::from(42u64);
+
+        let pk = gen_kary_tree_memb_crs::<
+            _,
+            E,
+            Fr,
+            PoseidonComScheme,
+            PoseidonComSchemeG,
+            PoseidonTreeH,
+            PoseidonTreeHG,
+            4,
+        >(&mut rng, crh_param.clone(), tree_height)
+        .unwrap();
+
+        let leaf_idx = 17;
+        let mut tree =
+            KaryComTree::<_, PoseidonTreeH, PoseidonComScheme, 4>::empty(crh_param, tree_height);
+        tree.insert(leaf_idx, &attrs_com);
+
+        let proof = tree
+            .prove_membership(&mut rng, &pk, leaf_idx, attrs_com)
+            .unwrap();
+
+        let vk: TreeVerifyingKey<_, Fr, _, _, _, _> = pk.prepare_verifying_key();
+        assert!(verify_tree_memb(&vk, &proof, &attrs_com, &tree.root()).unwrap());
+    }
+}
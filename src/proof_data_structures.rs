@@ -0,0 +1,81 @@
+//! Groth16 proof/proving-key/verifying-key wrappers shared across zkcreds circuits. Each wrapper
+//! pairs the underlying `ark_groth16` type with a `PhantomData` over the circuit's generic
+//! parameters, so a proof produced for one circuit instantiation can't be passed to a differently
+//! instantiated verifier by accident, even though the underlying `ark_groth16` types themselves
+//! carry none of that information.
+
+use crate::attrs::Attrs;
+
+use core::marker::PhantomData;
+
+use ark_crypto_primitives::{
+    commitment::{constraints::CommitmentGadget, CommitmentScheme},
+    crh::{constraints::TwoToOneCRHGadget, TwoToOneCRH},
+};
+use ark_ec::PairingEngine;
+
+/// A non-membership proof for [`RevocationTree::prove_nonmembership`](crate::revocation::RevocationTree::prove_nonmembership):
+/// evidence that a given revocation tag's slot holds the default (i.e. not-revoked) leaf value in
+/// the tree with a given root.
+pub struct RevocationProof<E, A, AC, ACG, H, HG>
+where
+    E: PairingEngine,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    H: TwoToOneCRH,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+{
+    pub(crate) proof: ark_groth16::Proof<E>,
+    pub(crate) _marker: PhantomData<(A, AC, ACG, H, HG)>,
+}
+
+/// A groth16 proving key for [`gen_revocation_crs`](crate::revocation::gen_revocation_crs). Shaped
+/// exactly like [`TreeProvingKey`], since the revocation tree is just a second instance of
+/// [`ComTreeConfig`](crate::com_tree::ComTreeConfig).
+pub struct RevocationProvingKey<E, A, AC, ACG, H, HG>
+where
+    E: PairingEngine,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    H: TwoToOneCRH,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+{
+    pub(crate) pk: ark_groth16::ProvingKey<E>,
+    pub(crate) _marker: PhantomData<(A, AC, ACG, H, HG)>,
+}
+
+impl<E, A, AC, ACG, H, HG> RevocationProvingKey<E, A, AC, ACG, H, HG>
+where
+    E: PairingEngine,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    H: TwoToOneCRH,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+{
+    /// Prepares this key's verifying key for repeated use in
+    /// [`verify_nonmembership`](crate::revocation::verify_nonmembership).
+    pub fn prepare_verifying_key(&self) -> RevocationVerifyingKey<E, A, AC, ACG, H, HG> {
+        RevocationVerifyingKey {
+            pvk: ark_groth16::prepare_verifying_key(&self.pk.vk),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A prepared verifying key for [`verify_nonmembership`](crate::revocation::verify_nonmembership),
+/// mirroring [`TreeVerifyingKey`]'s shape for the same kind of proof.
+pub struct RevocationVerifyingKey<E, A, AC, ACG, H, HG>
+where
+    E: PairingEngine,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    H: TwoToOneCRH,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+{
+    pub(crate) pvk: ark_groth16::PreparedVerifyingKey<E>,
+    pub(crate) _marker: PhantomData<(A, AC, ACG, H, HG)>,
+}
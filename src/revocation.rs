@@ -0,0 +1,332 @@
+//! Revocation via non-membership in a second sparse Merkle tree, keyed by a revocation tag (e.g. a
+//! PRF of the attribute commitment, computed off-circuit by the issuer and the holder alike).
+//!
+//! An unrevoked tag's slot holds the identity leaf hash's default value; revoking a credential
+//! means writing a non-default marker to its tag's slot. Because [`RevocationTree`] reuses
+//! [`ComTreeConfig`](crate::com_tree::ComTreeConfig)'s identity leaf hash, non-membership reduces
+//! to witnessing the auth path to the tag's slot and enforcing that the witnessed leaf equals
+//! `AC::Output::default()` while the recomputed root matches the public `root_var` — the mirror
+//! image of [`TreeMembershipProver`](crate::com_tree)'s membership check.
+
+use crate::{
+    attrs::Attrs,
+    com_tree::ComTreeConfig,
+    identity_crh::{IdentityCRHGadget, UnitVar},
+    proof_data_structures::{RevocationProof, RevocationProvingKey, RevocationVerifyingKey},
+    sparse_merkle::{constraints::SparseMerkleTreePathVar, SparseMerkleTree, SparseMerkleTreePath},
+};
+
+use core::marker::PhantomData;
+use std::collections::BTreeMap;
+
+use ark_crypto_primitives::{
+    commitment::{constraints::CommitmentGadget, CommitmentScheme},
+    crh::{constraints::TwoToOneCRHGadget, TwoToOneCRH},
+};
+use ark_ec::PairingEngine;
+use ark_ff::{to_bytes, PrimeField, ToConstraintField};
+use ark_r1cs_std::alloc::AllocVar;
+use ark_relations::{
+    ns,
+    r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError},
+};
+use ark_std::rand::Rng;
+
+/// A sparse Merkle tree of revocation tags. An unrevoked tag's slot is untouched (and so reads as
+/// `AC::Output::default()`); revoking a credential inserts a non-default marker at its tag.
+pub struct RevocationTree<ConstraintF, H, AC>
+where
+    ConstraintF: PrimeField,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF>,
+    AC: CommitmentScheme,
+    AC::Output: ToConstraintField<ConstraintF>,
+{
+    tree: SparseMerkleTree<ComTreeConfig<H>>,
+    _marker: PhantomData<(ConstraintF, AC)>,
+}
+
+impl<ConstraintF, H, AC> RevocationTree<ConstraintF, H, AC>
+where
+    ConstraintF: PrimeField,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF>,
+    AC: CommitmentScheme,
+    AC::Output: ToConstraintField<ConstraintF>,
+{
+    /// Returns this tree's root.
+    pub fn root(&self) -> H::Output {
+        self.tree.root()
+    }
+
+    /// Makes an empty revocation tree with capacity `2^tree_height`, i.e., one where every tag
+    /// currently reads as not-revoked.
+    pub fn empty(crh_params: H::Parameters, tree_height: u32) -> Self {
+        RevocationTree {
+            tree: SparseMerkleTree::empty::<AC::Output>((), crh_params, tree_height),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Marks the credential with the given revocation `tag` as revoked, by writing `marker`
+    /// (any value other than `AC::Output::default()`, conventionally the credential's own
+    /// attribute commitment) to its slot.
+    ///
+    /// Panics
+    /// =====
+    /// Panics when `tag >= 2^tree_height`
+    pub fn revoke(&mut self, tag: u64, marker: &AC::Output) {
+        self.tree
+            .insert(tag, marker)
+            .expect("could not insert revocation marker");
+    }
+
+    /// Un-revokes the credential at the given tag, restoring its slot to the default (unrevoked)
+    /// value.
+    pub fn unrevoke(&mut self, tag: u64) {
+        self.tree.remove(tag).expect("could not remove revocation marker");
+    }
+
+    /// Proves that the credential with revocation tag `tag` is NOT revoked, linking the proof to
+    /// `attrs_com` so a verifier can check it was produced for the same credential as a
+    /// corresponding [`TreeProof`](crate::proof_data_structures::TreeProof) of issuance.
+    pub fn prove_nonmembership<R, E, A, ACG, HG>(
+        &self,
+        rng: &mut R,
+        pk: &RevocationProvingKey<E, A, AC, ACG, H, HG>,
+        tag: u64,
+        attrs_com: AC::Output,
+    ) -> Result<RevocationProof<E, A, AC, ACG, H, HG>, SynthesisError>
+    where
+        R: Rng,
+        E: PairingEngine<Fr = ConstraintF>,
+        A: Attrs<E::Fr, AC>,
+        ACG: CommitmentGadget<AC, E::Fr>,
+        HG: TwoToOneCRHGadget<H, E::Fr>,
+    {
+        let root = self.tree.root();
+        // `generate_proof` errors when the tag's stored leaf doesn't match the expected
+        // (default/not-revoked) value, i.e. exactly when `tag` has been revoked — surface that as
+        // a proving failure rather than panicking, since a revoked tag is an expected input here,
+        // not a programmer error.
+        let auth_path = self
+            .tree
+            .generate_proof(tag, &AC::Output::default())
+            .map_err(|_| SynthesisError::Unsatisfiable)?;
+
+        let prover: RevocationNonmembershipProver<E::Fr, AC, ACG, H, HG> =
+            RevocationNonmembershipProver {
+                height: self.tree.height,
+                crh_param: self.tree.two_to_one_param.clone(),
+                attrs_com,
+                root,
+                auth_path: Some(auth_path),
+                _marker: PhantomData,
+            };
+
+        let proof = ark_groth16::create_random_proof(prover, &pk.pk, rng)?;
+        Ok(RevocationProof {
+            proof,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// Generates the non-membership proving key for a revocation tree of the given height.
+pub fn gen_revocation_crs<R, E, A, AC, ACG, H, HG>(
+    rng: &mut R,
+    crh_param: H::Parameters,
+    height: u32,
+) -> Result<RevocationProvingKey<E, A, AC, ACG, H, HG>, SynthesisError>
+where
+    R: Rng,
+    E: PairingEngine,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme,
+    AC::Output: ToConstraintField<E::Fr>,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<E::Fr>,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+{
+    let prover: RevocationNonmembershipProver<E::Fr, AC, ACG, H, HG> =
+        RevocationNonmembershipProver {
+            height,
+            crh_param,
+            attrs_com: Default::default(),
+            root: Default::default(),
+            auth_path: None,
+            _marker: PhantomData,
+        };
+    let pk = ark_groth16::generate_random_parameters(prover, rng)?;
+    Ok(RevocationProvingKey {
+        pk,
+        _marker: PhantomData,
+    })
+}
+
+#[cfg(test)]
+pub(crate) fn verify_nonmembership<E, A, AC, ACG, H, HG>(
+    vk: &RevocationVerifyingKey<E, A, AC, ACG, H, HG>,
+    proof: &RevocationProof<E, A, AC, ACG, H, HG>,
+    attrs_com: &AC::Output,
+    revocation_root: &H::Output,
+) -> Result<bool, SynthesisError>
+where
+    E: PairingEngine,
+    A: Attrs<E::Fr, AC>,
+    AC: CommitmentScheme,
+    ACG: CommitmentGadget<AC, E::Fr>,
+    AC::Output: ToConstraintField<E::Fr>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<E::Fr>,
+    HG: TwoToOneCRHGadget<H, E::Fr>,
+{
+    let attr_com_input = attrs_com.to_field_elements().unwrap();
+    let root_input = revocation_root.to_field_elements().unwrap();
+    let all_inputs = [attr_com_input, root_input].concat();
+    ark_groth16::verify_proof(&vk.pvk, &proof.proof, &all_inputs)
+}
+
+/// A circuit that proves the leaf at the witnessed position in the revocation tree of height
+/// `height` and root `root` equals `AC::Output::default()`, i.e., that whatever credential
+/// committed to by `attrs_com` sits at that tag is not revoked.
+struct RevocationNonmembershipProver<ConstraintF, AC, ACG, H, HG>
+where
+    ConstraintF: PrimeField,
+    AC: CommitmentScheme,
+    AC::Output: ToConstraintField<ConstraintF>,
+    ACG: CommitmentGadget<AC, ConstraintF>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF>,
+    HG: TwoToOneCRHGadget<H, ConstraintF>,
+{
+    height: u32,
+    crh_param: <H as TwoToOneCRH>::Parameters,
+
+    attrs_com: AC::Output,
+    root: H::Output,
+    auth_path: Option<SparseMerkleTreePath<ComTreeConfig<H>>>,
+
+    _marker: PhantomData<(ConstraintF, AC, ACG, HG)>,
+}
+
+impl<ConstraintF, AC, ACG, H, HG> ConstraintSynthesizer<ConstraintF>
+    for RevocationNonmembershipProver<ConstraintF, AC, ACG, H, HG>
+where
+    ConstraintF: PrimeField,
+    AC: CommitmentScheme,
+    AC::Output: ToConstraintField<ConstraintF> + Default,
+    ACG: CommitmentGadget<AC, ConstraintF>,
+    H: TwoToOneCRH,
+    H::Output: ToConstraintField<ConstraintF>,
+    HG: TwoToOneCRHGadget<H, ConstraintF>,
+{
+    fn generate_constraints(
+        self,
+        cs: ConstraintSystemRef<ConstraintF>,
+    ) -> Result<(), SynthesisError> {
+        // As in the membership proof, the public inputs are the attrs commitment (here only for
+        // linking to a companion issuance proof — it plays no role in the non-membership check
+        // itself) and the tree root.
+        let _attrs_com_var =
+            ACG::OutputVar::new_input(ns!(cs, "attrs com var"), || Ok(self.attrs_com))?;
+        let root_var = HG::OutputVar::new_input(ns!(cs, "root var"), || Ok(self.root))?;
+
+        let crh_param_var =
+            HG::ParametersVar::new_constant(ns!(cs, "two_to_one param"), &self.crh_param)?;
+        let leaf_param_var = UnitVar::default();
+
+        let auth_path = match self.auth_path {
+            Some(p) => p,
+            None => {
+                let default_bytes = to_bytes!(AC::Output::default()).unwrap();
+                SparseMerkleTreePath::<ComTreeConfig<H>> {
+                    leaf_hashes: (default_bytes.clone(), default_bytes),
+                    inner_hashes: vec![
+                        (H::Output::default(), H::Output::default());
+                        self.height
+                            .checked_sub(2)
+                            .expect("tree height cannot be < 2")
+                            as usize
+                    ],
+                }
+            }
+        };
+
+        let path_var = SparseMerkleTreePathVar::<_, IdentityCRHGadget, HG, _>::new_witness(
+            ns!(cs, "non-membership auth path"),
+            || Ok(auth_path),
+            self.height,
+        )?;
+
+        // The leaf we're proving membership of is the default (not-revoked) marker.
+        let default_leaf_var =
+            ACG::OutputVar::new_constant(ns!(cs, "default leaf"), AC::Output::default())?;
+
+        path_var.check_membership(
+            ns!(cs, "check_nonmembership").cs(),
+            &leaf_param_var,
+            &crh_param_var,
+            &root_var,
+            &default_leaf_var,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{
+        NameAndBirthYear, TestComScheme, TestComSchemeG, TestTreeH, TestTreeHG, MERKLE_CRH_PARAM,
+    };
+
+    use ark_bls12_381::Bls12_381 as E;
+
+    /// A credential that was never revoked should pass non-membership; revoking it should make
+    /// the same tag fail.
+    #[test]
+    fn test_revocation_roundtrip() {
+        let mut rng = ark_std::test_rng();
+        let tree_height = 32;
+
+        let person = NameAndBirthYear::new(&mut rng, b"Andrew", 1992);
+        let person_com = person.commit();
+        let tag = 17;
+
+        let pk = gen_revocation_crs::<
+            _,
+            E,
+            NameAndBirthYear,
+            TestComScheme,
+            TestComSchemeG,
+            TestTreeH,
+            TestTreeHG,
+        >(&mut rng, MERKLE_CRH_PARAM.clone(), tree_height)
+        .unwrap();
+        let vk = pk.prepare_verifying_key();
+
+        let mut tree =
+            RevocationTree::<_, TestTreeH, TestComScheme>::empty(MERKLE_CRH_PARAM.clone(), tree_height);
+
+        // Not revoked: non-membership proof succeeds.
+        let proof = tree
+            .prove_nonmembership(&mut rng, &pk, tag, person_com)
+            .unwrap();
+        assert!(verify_nonmembership(&vk, &proof, &person_com, &tree.root()).unwrap());
+
+        // After revocation, the same tag's slot is no longer default, so the prover can't
+        // construct a matching non-membership path against the new root.
+        tree.revoke(tag, &person_com);
+        assert_ne!(tree.root(), RevocationTree::<_, TestTreeH, TestComScheme>::empty(
+            MERKLE_CRH_PARAM.clone(),
+            tree_height,
+        ).root());
+
+        // The credential is now revoked: re-attempting the non-membership proof for the same tag
+        // must fail, since its slot no longer holds the default (not-revoked) marker.
+        assert!(tree
+            .prove_nonmembership(&mut rng, &pk, tag, person_com)
+            .is_err());
+    }
+}